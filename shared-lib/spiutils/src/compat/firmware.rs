@@ -23,6 +23,7 @@ use crate::protocol::wire::FromWire;
 use crate::protocol::wire::ToWireError;
 use crate::protocol::wire::ToWire;
 
+use core::fmt;
 use core::mem;
 
 // ----------------------------------------------------------------------------
@@ -77,3 +78,79 @@ impl ToWire for BuildInfo {
         Ok(())
     }
 }
+
+impl BuildInfo {
+    /// Returns true iff this build info was read from an erased segment, i.e.
+    /// every field is all-ones.
+    pub fn is_erased(&self) -> bool {
+        (self.epoch == u32::MAX) && (self.major == u32::MAX) && (self.minor == u32::MAX) &&
+            (self.timestamp == u64::MAX)
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_erased() {
+            return write!(f, "erased");
+        }
+        write!(f, "{}.{}.{}, built ", self.epoch, self.major, self.minor)?;
+        write_rfc3339(f, self.timestamp)
+    }
+}
+
+/// Writes `secs` (seconds since the Unix epoch) as an RFC 3339 UTC timestamp.
+fn write_rfc3339(f: &mut fmt::Formatter, secs: u64) -> fmt::Result {
+    let days = secs / 86400;
+    let secs_of_day = secs % 86400;
+
+    // Convert days since the epoch to a civil date. Taken from
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year, month, day,
+        secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn build_info(timestamp: u64) -> BuildInfo {
+        BuildInfo {
+            epoch: 0,
+            major: 1,
+            minor: 2,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(build_info(0).to_string(), "0.1.2, built 1970-01-01T00:00:00Z");
+        assert_eq!(build_info(951782400).to_string(), "0.1.2, built 2000-02-29T00:00:00Z");
+        assert_eq!(build_info(1709164800 + 45296).to_string(), "0.1.2, built 2024-02-29T12:34:56Z");
+        assert_eq!(build_info(4102444799).to_string(), "0.1.2, built 2099-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn display_erased() {
+        let erased = BuildInfo::from_wire(&[0xff; BUILD_INFO_LEN][..]).expect("from_wire failed");
+        assert!(erased.is_erased());
+        assert_eq!(erased.to_string(), "erased");
+
+        assert!(!build_info(0).is_erased());
+
+        let mut partial = erased;
+        partial.timestamp = 0;
+        assert!(!partial.is_erased());
+    }
+}
@@ -25,6 +25,7 @@ use crate::protocol::wire::ToWireError;
 use crate::protocol::wire::ToWire;
 use crate::protocol::wire::WireEnum;
 
+use core::fmt;
 use core::mem;
 
 /// The length of a SegmentInfo on the wire, in bytes.
@@ -58,6 +59,21 @@ pub struct SegmentInfo {
     pub page_count: u32,
 }
 
+impl fmt::Display for SegmentInfo {
+    /// Formats the segment with its flash offsets in hex, its size in KiB and
+    /// the range of flash pages it occupies.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let end = self.address as u64 + self.size as u64;
+        write!(f, "{} @ 0x{:08x}..0x{:08x}", self.identifier, self.address, end)?;
+        if self.size % 1024 == 0 {
+            write!(f, " ({} KiB", self.size / 1024)?;
+        } else {
+            write!(f, " ({} bytes", self.size)?;
+        }
+        write!(f, ", pages {}..{})", self.start_page, self.start_page as u64 + self.page_count as u64)
+    }
+}
+
 impl<'a> FromWire<'a> for SegmentInfo {
     fn from_wire<R: Read<'a>>(mut r: R) -> Result<Self, FromWireError> {
         let sal_u8 = r.read_be::<u8>()?;
@@ -142,3 +158,37 @@ impl ToWire for RuntimeSegmentInfo {
 }
 
 // ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display_kib() {
+        let segment = SegmentInfo {
+            identifier: SegmentAndLocation::RoA,
+            address: 0x4000,
+            size: 0x20000,
+            start_page: 8,
+            page_count: 64,
+        };
+        assert_eq!(segment.to_string(), "RoA @ 0x00004000..0x00024000 (128 KiB, pages 8..72)");
+    }
+
+    #[test]
+    fn display_bytes() {
+        let segment = SegmentInfo {
+            identifier: SegmentAndLocation::RwB,
+            address: 0x80000,
+            size: 0x1234,
+            start_page: 256,
+            page_count: 3,
+        };
+        assert_eq!(segment.to_string(), "RwB @ 0x00080000..0x00081234 (4660 bytes, pages 256..259)");
+    }
+
+    #[test]
+    fn display_unknown() {
+        assert_eq!(UNKNOWN_SEGMENT.to_string(), "Unknown @ 0x00000000..0x00000000 (0 KiB, pages 0..0)");
+    }
+}
@@ -54,6 +54,24 @@ wire_enum! {
 
         /// Response to RebootRequest
         RebootResponse = 0x08,
+
+        /// Request information on active segments
+        ActiveSegmentsInfoRequest = 0x09,
+
+        /// Response to ActiveSegmentsInfoRequest
+        ActiveSegmentsInfoResponse = 0x0a,
+
+        /// Request to read back a chunk of firmware
+        ReadChunkRequest = 0x0b,
+
+        /// Response to ReadChunkRequest
+        ReadChunkResponse = 0x0c,
+
+        /// Request the progress of the current update
+        UpdateStatusRequest = 0x0d,
+
+        /// Response to UpdateStatusRequest
+        UpdateStatusResponse = 0x0e,
     }
 }
 
@@ -188,6 +206,70 @@ impl ToWire for InactiveSegmentsInfoResponse {
 
 // ----------------------------------------------------------------------------
 
+/// A parsed active segments info request.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ActiveSegmentsInfoRequest {
+}
+
+/// The length of an active segments info request on the wire, in bytes.
+pub const ACTIVE_SEGMENTS_INFO_REQUEST_LEN: usize = 0;
+
+impl Message<'_> for ActiveSegmentsInfoRequest {
+    const TYPE: ContentType = ContentType::ActiveSegmentsInfoRequest;
+}
+
+impl<'a> FromWire<'a> for ActiveSegmentsInfoRequest {
+    fn from_wire<R: Read<'a>>(mut _r: R) -> Result<Self, FromWireError> {
+        Ok(Self {})
+    }
+}
+
+impl ToWire for ActiveSegmentsInfoRequest {
+    fn to_wire<W: Write>(&self, mut _w: W) -> Result<(), ToWireError> {
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A parsed active segments info response.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ActiveSegmentsInfoResponse {
+    /// The active RO.
+    pub ro: SegmentInfo,
+
+    /// The active RW.
+    pub rw: SegmentInfo,
+}
+
+/// The length of an active segments info response on the wire, in bytes.
+pub const ACTIVE_SEGMENTS_INFO_RESPONSE_LEN: usize = 2 * SEGMENT_INFO_LEN;
+
+impl Message<'_> for ActiveSegmentsInfoResponse {
+    const TYPE: ContentType = ContentType::ActiveSegmentsInfoResponse;
+}
+
+impl<'a> FromWire<'a> for ActiveSegmentsInfoResponse {
+    fn from_wire<R: Read<'a>>(mut r: R) -> Result<Self, FromWireError> {
+        let ro = SegmentInfo::from_wire(&mut r)?;
+        let rw = SegmentInfo::from_wire(&mut r)?;
+        Ok(Self {
+            ro,
+            rw,
+        })
+    }
+}
+
+impl ToWire for ActiveSegmentsInfoResponse {
+    fn to_wire<W: Write>(&self, mut w: W) -> Result<(), ToWireError> {
+        self.ro.to_wire(&mut w)?;
+        self.rw.to_wire(&mut w)?;
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// A parsed firmware info message.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct FirmwareInfo {
@@ -431,6 +513,207 @@ impl ToWire for WriteChunkResponse {
 
 // ----------------------------------------------------------------------------
 
+/// A parsed read chunk request.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ReadChunkRequest {
+    /// The segment and location.
+    pub segment_and_location: SegmentAndLocation,
+
+    /// The offset within the segment. Must be 4-byte aligned.
+    pub offset: u32,
+
+    /// The number of bytes to read. Must be non-zero (otherwise the result is
+    /// `InvalidLength`) and no larger than `MAX_READ_CHUNK_LEN` (otherwise the
+    /// result is `DataTooLong`).
+    pub length: u16,
+}
+
+/// The length of a read chunk request on the wire, in bytes.
+pub const READ_CHUNK_REQUEST_LEN: usize = 7;
+
+/// The maximum number of bytes that can be read with a single read chunk
+/// request. The device enforces this limit; it must not exceed the size of
+/// the device's flash read buffer.
+pub const MAX_READ_CHUNK_LEN: usize = 128;
+
+impl Message<'_> for ReadChunkRequest {
+    const TYPE: ContentType = ContentType::ReadChunkRequest;
+}
+
+impl<'a> FromWire<'a> for ReadChunkRequest {
+    fn from_wire<R: Read<'a>>(mut r: R) -> Result<Self, FromWireError> {
+        let sal_u8 = r.read_be::<u8>()?;
+        let segment_and_location = SegmentAndLocation::from_wire_value(sal_u8).ok_or(FromWireError::OutOfRange)?;
+        let offset = r.read_be::<u32>()?;
+        let length = r.read_be::<u16>()?;
+        Ok(Self {
+            segment_and_location,
+            offset,
+            length,
+        })
+    }
+}
+
+impl ToWire for ReadChunkRequest {
+    fn to_wire<W: Write>(&self, mut w: W) -> Result<(), ToWireError> {
+        w.write_be(self.segment_and_location.to_wire_value())?;
+        w.write_be(self.offset)?;
+        w.write_be(self.length)?;
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+wire_enum! {
+    /// The result of a read chunk request.
+    pub enum ReadChunkResult: u8 {
+        /// Success
+        Success = 0x00,
+
+        /// Unspecified error
+        Error = 0x01,
+
+        /// Invalid segment and/or location
+        InvalidSegmentAndLocation = 0x02,
+
+        /// Invalid offset
+        InvalidOffset = 0x03,
+
+        /// Too much data requested
+        DataTooLong = 0x04,
+
+        /// Zero bytes requested
+        InvalidLength = 0x05,
+    }
+}
+
+/// A parsed read chunk response.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ReadChunkResponse<'a> {
+    /// The segment and location.
+    pub segment_and_location: SegmentAndLocation,
+
+    /// The offset within the segment.
+    pub offset: u32,
+
+    /// The result of the read chunk request.
+    pub result: ReadChunkResult,
+
+    /// The data read. Empty unless the result is `Success`.
+    pub data: &'a [u8],
+}
+
+/// The length of a read chunk response on the wire, in bytes.
+pub const READ_CHUNK_RESPONSE_LEN: usize = 6;
+
+impl<'a> Message<'a> for ReadChunkResponse<'a> {
+    const TYPE: ContentType = ContentType::ReadChunkResponse;
+}
+
+impl<'a> FromWire<'a> for ReadChunkResponse<'a> {
+    fn from_wire<R: Read<'a>>(mut r: R) -> Result<Self, FromWireError> {
+        let sal_u8 = r.read_be::<u8>()?;
+        let segment_and_location = SegmentAndLocation::from_wire_value(sal_u8).ok_or(FromWireError::OutOfRange)?;
+        let offset = r.read_be::<u32>()?;
+        let result_u8 = r.read_be::<u8>()?;
+        let result = ReadChunkResult::from_wire_value(result_u8).ok_or(FromWireError::OutOfRange)?;
+        let data_len = r.remaining_data();
+        let data = r.read_bytes(data_len)?;
+        Ok(Self {
+            segment_and_location,
+            offset,
+            result,
+            data,
+        })
+    }
+}
+
+impl ToWire for ReadChunkResponse<'_> {
+    fn to_wire<W: Write>(&self, mut w: W) -> Result<(), ToWireError> {
+        w.write_be(self.segment_and_location.to_wire_value())?;
+        w.write_be(self.offset)?;
+        w.write_be(self.result.to_wire_value())?;
+        w.write_bytes(self.data)?;
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A parsed update status request.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct UpdateStatusRequest {
+}
+
+/// The length of an update status request on the wire, in bytes.
+pub const UPDATE_STATUS_REQUEST_LEN: usize = 0;
+
+impl Message<'_> for UpdateStatusRequest {
+    const TYPE: ContentType = ContentType::UpdateStatusRequest;
+}
+
+impl<'a> FromWire<'a> for UpdateStatusRequest {
+    fn from_wire<R: Read<'a>>(mut _r: R) -> Result<Self, FromWireError> {
+        Ok(Self {})
+    }
+}
+
+impl ToWire for UpdateStatusRequest {
+    fn to_wire<W: Write>(&self, mut _w: W) -> Result<(), ToWireError> {
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A parsed update status response.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct UpdateStatusResponse {
+    /// The segment and location being updated.
+    /// `Unknown` if no update has been prepared.
+    pub segment_and_location: SegmentAndLocation,
+
+    /// The offset following the data written contiguously from the start of
+    /// the segment since the update was prepared. Writing can be resumed at
+    /// this offset. A failed write below this offset moves it back to the
+    /// failed chunk.
+    ///
+    /// The device keeps this progress in RAM only. It is lost on reboot, after
+    /// which `segment_and_location` is `Unknown` until the next update is
+    /// prepared.
+    pub next_offset: u32,
+}
+
+/// The length of an update status response on the wire, in bytes.
+pub const UPDATE_STATUS_RESPONSE_LEN: usize = 5;
+
+impl Message<'_> for UpdateStatusResponse {
+    const TYPE: ContentType = ContentType::UpdateStatusResponse;
+}
+
+impl<'a> FromWire<'a> for UpdateStatusResponse {
+    fn from_wire<R: Read<'a>>(mut r: R) -> Result<Self, FromWireError> {
+        let sal_u8 = r.read_be::<u8>()?;
+        let segment_and_location = SegmentAndLocation::from_wire_value(sal_u8).ok_or(FromWireError::OutOfRange)?;
+        let next_offset = r.read_be::<u32>()?;
+        Ok(Self {
+            segment_and_location,
+            next_offset,
+        })
+    }
+}
+
+impl ToWire for UpdateStatusResponse {
+    fn to_wire<W: Write>(&self, mut w: W) -> Result<(), ToWireError> {
+        w.write_be(self.segment_and_location.to_wire_value())?;
+        w.write_be(self.next_offset)?;
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 wire_enum! {
     /// When to perform the reboot.
     pub enum RebootTime: u8 {
@@ -90,7 +90,7 @@ impl From<io::Error> for ToWireError {
 ///
 /// Also, the following identity must hold for all types T:
 /// ```
-/// # use manticore::protocol::wire::WireEnum;
+/// # use spiutils::protocol::wire::WireEnum;
 /// # fn test<T: WireEnum + Copy + PartialEq + std::fmt::Debug>(x: T) {
 /// assert_eq!(T::from_name(T::name(x)), Some(x));
 /// # }
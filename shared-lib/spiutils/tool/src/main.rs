@@ -21,10 +21,21 @@ use clap::SubCommand;
 
 use core::convert::TryFrom;
 
+use manticore::mem::BumpArena;
+use manticore::protocol::wire::FromWire as _;
+
 use spiutils::io::StdWrite;
 use spiutils::io::Write;
+use spiutils::protocol::error;
+use spiutils::protocol::firmware;
+use spiutils::protocol::flash;
+use spiutils::protocol::flash::Address;
+use spiutils::protocol::flash::AddressMode;
+use spiutils::protocol::flash::OpCode;
 use spiutils::protocol::payload;
 use spiutils::protocol::wire::FromWire;
+use spiutils::protocol::wire::FromWireError;
+use spiutils::protocol::wire::WireEnum;
 use spiutils::protocol::wire::ToWire;
 
 use std::fs::OpenOptions;
@@ -96,6 +107,325 @@ fn unwrap(input_file: &str, output_file: &str) {
     }
 }
 
+fn format_decoded<T: core::fmt::Debug>(result: Result<T, FromWireError>) -> String {
+    match result {
+        Ok(msg) => format!("  {:?}", msg),
+        Err(why) => format!("  failed to decode: {:?}", why),
+    }
+}
+
+fn decode_firmware(mut data: &[u8]) -> String {
+    let header = match firmware::Header::from_wire(&mut data) {
+        Ok(header) => header,
+        Err(why) => {
+            return format!("  Firmware: failed to read header: {:?}", why);
+        }
+    };
+
+    match header.content {
+        firmware::ContentType::UpdatePrepareRequest => {
+            format_decoded(firmware::UpdatePrepareRequest::from_wire(&mut data))
+        }
+        firmware::ContentType::UpdatePrepareResponse => {
+            format_decoded(firmware::UpdatePrepareResponse::from_wire(&mut data))
+        }
+        firmware::ContentType::WriteChunkRequest => {
+            match firmware::WriteChunkRequest::from_wire(&mut data) {
+                Ok(req) => format!("  WriteChunkRequest {{ segment_and_location: {}, offset: 0x{:x}, data_len: {} }}",
+                    req.segment_and_location, req.offset, req.data.len()),
+                Err(why) => format!("  WriteChunkRequest: {:?}", why),
+            }
+        }
+        firmware::ContentType::WriteChunkResponse => {
+            format_decoded(firmware::WriteChunkResponse::from_wire(&mut data))
+        }
+        firmware::ContentType::InactiveSegmentsInfoRequest => {
+            format_decoded(firmware::InactiveSegmentsInfoRequest::from_wire(&mut data))
+        }
+        firmware::ContentType::InactiveSegmentsInfoResponse => {
+            match firmware::InactiveSegmentsInfoResponse::from_wire(&mut data) {
+                Ok(resp) => format!("  InactiveSegmentsInfoResponse\n    ro: {}\n    rw: {}", resp.ro, resp.rw),
+                Err(why) => format!("  InactiveSegmentsInfoResponse: {:?}", why),
+            }
+        }
+        firmware::ContentType::ActiveSegmentsInfoRequest => {
+            format_decoded(firmware::ActiveSegmentsInfoRequest::from_wire(&mut data))
+        }
+        firmware::ContentType::ActiveSegmentsInfoResponse => {
+            match firmware::ActiveSegmentsInfoResponse::from_wire(&mut data) {
+                Ok(resp) => format!("  ActiveSegmentsInfoResponse\n    ro: {}\n    rw: {}", resp.ro, resp.rw),
+                Err(why) => format!("  ActiveSegmentsInfoResponse: {:?}", why),
+            }
+        }
+        firmware::ContentType::ReadChunkRequest => {
+            match firmware::ReadChunkRequest::from_wire(&mut data) {
+                Ok(req) => format!("  ReadChunkRequest {{ segment_and_location: {}, offset: 0x{:x}, length: {} }}",
+                    req.segment_and_location, req.offset, req.length),
+                Err(why) => format!("  ReadChunkRequest: {:?}", why),
+            }
+        }
+        firmware::ContentType::ReadChunkResponse => {
+            match firmware::ReadChunkResponse::from_wire(&mut data) {
+                Ok(resp) => format!("  ReadChunkResponse {{ segment_and_location: {}, offset: 0x{:x}, result: {}, data_len: {} }}",
+                    resp.segment_and_location, resp.offset, resp.result, resp.data.len()),
+                Err(why) => format!("  ReadChunkResponse: {:?}", why),
+            }
+        }
+        firmware::ContentType::UpdateStatusRequest => {
+            format_decoded(firmware::UpdateStatusRequest::from_wire(&mut data))
+        }
+        firmware::ContentType::UpdateStatusResponse => {
+            format_decoded(firmware::UpdateStatusResponse::from_wire(&mut data))
+        }
+        firmware::ContentType::RebootRequest => {
+            format_decoded(firmware::RebootRequest::from_wire(&mut data))
+        }
+        firmware::ContentType::RebootResponse => {
+            format_decoded(firmware::RebootResponse::from_wire(&mut data))
+        }
+    }
+}
+
+fn decode_manticore(mut data: &[u8]) -> Vec<String> {
+    let mut arena_buf = [0u8; 64];
+    let arena = BumpArena::new(&mut arena_buf[..]);
+    let mut lines = Vec::new();
+    match manticore::protocol::Header::from_wire(&mut data, &arena) {
+        Ok(header) => lines.push(format!("  {:?}", header)),
+        Err(why) => {
+            lines.push(format!("  Manticore header: {:?}", why));
+            return lines;
+        }
+    }
+    if !data.is_empty() {
+        lines.push(format!("  {:02x?}", data));
+    }
+    lines
+}
+
+/// Decodes the payloads in a mailbox dump, returning one or more lines of
+/// output per payload.
+fn decode_payloads(buf: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    while buf.len() - offset >= payload::HEADER_LEN {
+        let mut data = &buf[offset..];
+        if data.iter().all(|&b| b == 0xff) {
+            // The rest of the capture is an empty mailbox.
+            break;
+        }
+
+        let header = match payload::Header::from_wire(&mut data) {
+            Ok(header) => header,
+            Err(why) => {
+                lines.push(format!("0x{:04x}: failed to read payload header: {:?}", offset, why));
+                break;
+            }
+        };
+        let content_len = header.content_len as usize;
+        if data.len() < content_len {
+            lines.push(format!("0x{:04x}: {:?} truncated, expected {} bytes, found {}",
+                offset, header.content, content_len, data.len()));
+            break;
+        }
+
+        let checksum = payload::compute_checksum(&header, data);
+        lines.push(format!("0x{:04x}: {:?}, {} bytes, checksum 0x{:02x} ({})",
+            offset, header.content, content_len, header.checksum,
+            if checksum == header.checksum { "ok" } else { "BAD" }));
+
+        let content = &data[..content_len];
+        match header.content {
+            payload::ContentType::Error => {
+                lines.push(format_decoded(error::Header::from_wire(content)));
+            }
+            payload::ContentType::Manticore => {
+                lines.extend(decode_manticore(content));
+            }
+            payload::ContentType::Firmware => {
+                lines.push(decode_firmware(content));
+            }
+        }
+
+        offset += payload::HEADER_LEN + content_len;
+    }
+    lines
+}
+
+fn print_payloads(buf: &[u8]) {
+    for line in decode_payloads(buf) {
+        println!("{}", line);
+    }
+}
+
+/// A single SPI transaction (one chip select assertion) from a capture.
+struct SpiTransaction {
+    mosi: Vec<u8>,
+    miso: Vec<u8>,
+}
+
+/// Parses a Saleae Logic SPI analyzer CSV export.
+///
+/// The expected columns are `Time [s],Packet ID,MOSI,MISO`, with one row per
+/// byte and all bytes of a transaction sharing the same packet ID.
+fn parse_saleae_csv(text: &str) -> Vec<SpiTransaction> {
+    let mut transactions = Vec::new();
+    let mut packet_id: Option<&str> = None;
+
+    for (line_nr, line) in text.lines().enumerate().skip(1) {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let parse_byte = |field: &str| {
+            u8::from_str_radix(field.trim_start_matches("0x"), 16)
+                .unwrap_or_else(|_| panic!("invalid byte '{}' on line {}", field, line_nr + 1))
+        };
+
+        if packet_id != Some(fields[1]) {
+            packet_id = Some(fields[1]);
+            transactions.push(SpiTransaction {
+                mosi: Vec::new(),
+                miso: Vec::new(),
+            });
+        }
+        let transaction = transactions.last_mut().unwrap();
+        transaction.mosi.push(parse_byte(fields[2]));
+        transaction.miso.push(parse_byte(fields[3]));
+    }
+
+    transactions
+}
+
+/// Parses the SPI transfer annotations printed by sigrok-cli, e.g.
+///
+///     sigrok-cli -i capture.sr -P spi:clk=D0:mosi=D1:miso=D2:cs=D3 \
+///         -A spi=mosi-transfer:miso-transfer --protocol-decoder-ann-class
+///
+/// Each line carries the bytes of one direction of a transaction, e.g.
+/// `spi-1: mosi-transfer: 03 08 00 00 00 00`. The MOSI and MISO lines of a
+/// transaction may come in either order. Other lines are skipped.
+fn parse_sigrok_transfers(text: &str) -> Vec<SpiTransaction> {
+    let mut transactions = Vec::new();
+    let mut mosi: Option<Vec<u8>> = None;
+    let mut miso: Option<Vec<u8>> = None;
+
+    for (line_nr, line) in text.lines().enumerate() {
+        let fields: Vec<&str> = line.splitn(3, ": ").map(|f| f.trim()).collect();
+        if fields.len() < 3 {
+            continue;
+        }
+        let slot = match fields[1] {
+            "mosi-transfer" => &mut mosi,
+            "miso-transfer" => &mut miso,
+            _ => continue,
+        };
+        let data = fields[2].trim_matches('"')
+            .split_whitespace()
+            .map(|byte| u8::from_str_radix(byte, 16)
+                .unwrap_or_else(|_| panic!("invalid byte '{}' on line {}", byte, line_nr + 1)))
+            .collect();
+        *slot = Some(data);
+
+        if mosi.is_some() && miso.is_some() {
+            transactions.push(SpiTransaction {
+                mosi: mosi.take().unwrap(),
+                miso: miso.take().unwrap(),
+            });
+        }
+    }
+
+    transactions
+}
+
+/// The mailbox data carried by a single SPI transaction.
+#[derive(Debug, PartialEq)]
+enum MailboxAccess<'a> {
+    /// The host wrote the given data to the mailbox.
+    Write(&'a [u8]),
+
+    /// The host read the given data from the mailbox.
+    Read(&'a [u8]),
+
+    /// The host read from the mailbox using a dual-output read. The data was
+    /// returned on two I/O lines and cannot be reconstructed from MISO alone.
+    DualOutputRead,
+}
+
+fn get_mailbox_access_with_address<'a, AddrType: Address>(
+        transaction: &'a SpiTransaction, mailbox_addr: u32) -> Option<MailboxAccess<'a>> {
+    let mut mosi = transaction.mosi.as_slice();
+    let header = flash::Header::<AddrType>::from_wire(&mut mosi).ok()?;
+    if header.get_address() != Some(mailbox_addr) {
+        return None;
+    }
+    let header_len = transaction.mosi.len() - mosi.len();
+
+    match header.opcode {
+        OpCode::PageProgram => Some(MailboxAccess::Write(mosi)),
+        OpCode::NormalRead | OpCode::FastRead | OpCode::FastRead4B => {
+            Some(MailboxAccess::Read(transaction.miso.get(header_len..).unwrap_or(&[])))
+        }
+        OpCode::FastReadDualOutput => Some(MailboxAccess::DualOutputRead),
+        _ => None,
+    }
+}
+
+fn get_mailbox_access(
+        transaction: &SpiTransaction, mailbox_addr: u32, addr_mode: AddressMode) -> Option<MailboxAccess<'_>> {
+    // FastRead4B always carries a 4 byte address, regardless of the address mode.
+    let is_fast_read_4b = transaction.mosi.first() == Some(&OpCode::FastRead4B.to_wire_value());
+    match addr_mode {
+        AddressMode::ThreeByte if !is_fast_read_4b =>
+            get_mailbox_access_with_address::<ux::u24>(transaction, mailbox_addr),
+        _ => get_mailbox_access_with_address::<u32>(transaction, mailbox_addr),
+    }
+}
+
+fn decode_transaction(index: usize, transaction: &SpiTransaction, mailbox_addr: u32, addr_mode: AddressMode) {
+    match get_mailbox_access(transaction, mailbox_addr, addr_mode) {
+        Some(MailboxAccess::Write(data)) => {
+            println!("Transaction {}: mailbox write", index);
+            print_payloads(data);
+        }
+        Some(MailboxAccess::Read(data)) => {
+            println!("Transaction {}: mailbox read", index);
+            print_payloads(data);
+        }
+        Some(MailboxAccess::DualOutputRead) => {
+            println!("Transaction {}: mailbox dual-output read, not decodable", index);
+        }
+        None => {}
+    }
+}
+
+fn decode(input_file: &str, format: &str, mailbox_addr: u32, addr_mode: AddressMode) {
+    let mut input = OpenOptions::new()
+        .read(true)
+        .open(input_file)
+        .expect("failed to open input file");
+
+    let mut read_buf = Vec::new();
+    input
+        .read_to_end(&mut read_buf)
+        .expect("couldn't read from file");
+
+    match format {
+        "raw" => print_payloads(&read_buf),
+        "saleae" | "sigrok" => {
+            let text = String::from_utf8(read_buf).expect("capture is not valid UTF-8");
+            let transactions = match format {
+                "saleae" => parse_saleae_csv(&text),
+                _ => parse_sigrok_transfers(&text),
+            };
+            for (index, transaction) in transactions.iter().enumerate() {
+                decode_transaction(index, transaction, mailbox_addr, addr_mode);
+            }
+        }
+        _ => panic!("Unsupported capture format {}", format),
+    }
+}
+
 fn main() {
     let app = App::new("SPI Transport Tool")
         .version("0.1")
@@ -148,6 +478,41 @@ fn main() {
                         .required(true)
                         .takes_value(true),
                 ),
+        )
+        .subcommand(
+            SubCommand::with_name("decode")
+                .about("Decode the payloads in a mailbox capture")
+                .arg(
+                    Arg::with_name("file")
+                        .short("f")
+                        .long("file")
+                        .help("input file containing the capture")
+                        .required(true)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .help("format of the capture")
+                        .possible_values(&["raw", "saleae", "sigrok"])
+                        .default_value("raw")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("mailbox-addr")
+                        .long("mailbox-addr")
+                        .help("address of the mailbox on the SPI bus (saleae and sigrok only)")
+                        .default_value("0x80000")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("addr-mode")
+                        .long("addr-mode")
+                        .help("SPI flash address mode used in the capture (saleae and sigrok only)")
+                        .possible_values(&["3b", "4b"])
+                        .default_value("4b")
+                        .takes_value(true),
+                ),
         );
     let matches = app.get_matches();
 
@@ -161,5 +526,179 @@ fn main() {
             matches.value_of("input").unwrap(),
             matches.value_of("output").unwrap(),
         );
+    } else if let Some(matches) = matches.subcommand_matches("decode") {
+        let mailbox_addr = matches.value_of("mailbox-addr").unwrap();
+        let mailbox_addr = u32::from_str_radix(mailbox_addr.trim_start_matches("0x"), 16)
+            .expect("invalid mailbox address");
+        let addr_mode = match matches.value_of("addr-mode").unwrap() {
+            "3b" => AddressMode::ThreeByte,
+            _ => AddressMode::FourByte,
+        };
+        decode(
+            matches.value_of("file").unwrap(),
+            matches.value_of("format").unwrap(),
+            mailbox_addr,
+            addr_mode,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAILBOX_ADDR: u32 = 0x80000;
+
+    fn frame(content: payload::ContentType, body: &[u8]) -> Vec<u8> {
+        let mut header = payload::Header {
+            content,
+            content_len: u16::try_from(body.len()).unwrap(),
+            checksum: 0,
+        };
+        header.checksum = payload::compute_checksum(&header, body);
+
+        let mut buf = Vec::new();
+        header.to_wire(StdWrite(&mut buf)).expect("failed to write header");
+        buf.extend_from_slice(body);
+        buf
+    }
+
+    fn firmware_body<'a, M: firmware::Message<'a>>(msg: M) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let header = firmware::Header {
+            content: M::TYPE,
+        };
+        header.to_wire(StdWrite(&mut buf)).expect("failed to write header");
+        msg.to_wire(StdWrite(&mut buf)).expect("failed to write message");
+        buf
+    }
+
+    #[test]
+    fn raw_checksums_and_empty_mailbox() {
+        let mut buf = frame(payload::ContentType::Firmware,
+            &firmware_body(firmware::UpdateStatusRequest {}));
+        let second_offset = buf.len();
+        let mut bad = frame(payload::ContentType::Firmware,
+            &firmware_body(firmware::UpdateStatusResponse {
+                segment_and_location: firmware::SegmentAndLocation::RwA,
+                next_offset: 0x100,
+            }));
+        bad[3] ^= 0xff;
+        let bad_checksum = bad[3];
+        buf.extend_from_slice(&bad);
+        buf.extend_from_slice(&[0xff; 32]);
+
+        let lines = decode_payloads(&buf);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("0x0000: Firmware, 1 bytes, checksum "));
+        assert!(lines[0].ends_with("(ok)"));
+        assert_eq!(lines[1], "  UpdateStatusRequest");
+        assert_eq!(lines[2], format!("0x{:04x}: Firmware, 6 bytes, checksum 0x{:02x} (BAD)",
+            second_offset, bad_checksum));
+        assert_eq!(lines[3], "  UpdateStatusResponse { segment_and_location: RwA, next_offset: 256 }");
+    }
+
+    #[test]
+    fn raw_truncated() {
+        let mut buf = frame(payload::ContentType::Firmware, &[0u8; 8]);
+        buf.truncate(payload::HEADER_LEN + 2);
+        buf.extend_from_slice(&frame(payload::ContentType::Firmware, &[]));
+
+        let lines = decode_payloads(&buf);
+        assert_eq!(lines, vec!["0x0000: Firmware truncated, expected 8 bytes, found 6"]);
+    }
+
+    #[test]
+    fn raw_manticore_without_header() {
+        let buf = frame(payload::ContentType::Manticore, &[]);
+
+        let lines = decode_payloads(&buf);
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("  Manticore header: "));
+    }
+
+    #[test]
+    fn saleae_groups_by_packet_id() {
+        let csv = "Time [s],Packet ID,MOSI,MISO\n\
+                   0.000001,0,0x06,0xFF\n\
+                   0.000002,1,0x05,0xFF\n\
+                   0.000003,1,0x00,0x02\n\
+                   0.000004,1\n\
+                   0.000005,2,0x04,0xFF\n";
+        let transactions = parse_saleae_csv(csv);
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(transactions[0].mosi, vec![0x06]);
+        assert_eq!(transactions[1].mosi, vec![0x05, 0x00]);
+        assert_eq!(transactions[1].miso, vec![0xff, 0x02]);
+        assert_eq!(transactions[2].mosi, vec![0x04]);
+    }
+
+    #[test]
+    fn sigrok_pairs_mosi_and_miso() {
+        let text = "spi-1: mosi-transfer: 06\n\
+                    spi-1: miso-transfer: FF\n\
+                    spi-1: mosi-data: 05\n\
+                    spi-1: miso-transfer: \"FF 02\"\n\
+                    spi-1: mosi-transfer: \"05 00\"\n\
+                    spi-1: warning\n";
+        let transactions = parse_sigrok_transfers(text);
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].mosi, vec![0x06]);
+        assert_eq!(transactions[0].miso, vec![0xff]);
+        assert_eq!(transactions[1].mosi, vec![0x05, 0x00]);
+        assert_eq!(transactions[1].miso, vec![0xff, 0x02]);
+    }
+
+    #[test]
+    fn mailbox_write() {
+        let transaction = SpiTransaction {
+            mosi: vec![0x02, 0x00, 0x08, 0x00, 0x00, 0x02, 0x00, 0x01, 0x22, 0x05],
+            miso: vec![0xff; 10],
+        };
+        assert_eq!(get_mailbox_access(&transaction, MAILBOX_ADDR, AddressMode::FourByte),
+            Some(MailboxAccess::Write(&[0x02, 0x00, 0x01, 0x22, 0x05])));
+
+        // Writes elsewhere are not mailbox accesses.
+        assert_eq!(get_mailbox_access(&transaction, 0x0, AddressMode::FourByte), None);
+    }
+
+    #[test]
+    fn mailbox_read() {
+        let transaction = SpiTransaction {
+            mosi: vec![0x03, 0x08, 0x00, 0x00, 0x00, 0x00],
+            miso: vec![0xff, 0xff, 0xff, 0xff, 0x02, 0x00],
+        };
+        assert_eq!(get_mailbox_access(&transaction, MAILBOX_ADDR, AddressMode::ThreeByte),
+            Some(MailboxAccess::Read(&[0x02, 0x00])));
+    }
+
+    #[test]
+    fn mailbox_fast_read_skips_dummy_byte() {
+        let transaction = SpiTransaction {
+            mosi: vec![0x0b, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00],
+            miso: vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02, 0x00],
+        };
+        assert_eq!(get_mailbox_access(&transaction, MAILBOX_ADDR, AddressMode::FourByte),
+            Some(MailboxAccess::Read(&[0x02, 0x00])));
+    }
+
+    #[test]
+    fn mailbox_fast_read_4b_in_three_byte_mode() {
+        let transaction = SpiTransaction {
+            mosi: vec![0x0c, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00],
+            miso: vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02, 0x00],
+        };
+        assert_eq!(get_mailbox_access(&transaction, MAILBOX_ADDR, AddressMode::ThreeByte),
+            Some(MailboxAccess::Read(&[0x02, 0x00])));
+    }
+
+    #[test]
+    fn mailbox_dual_output_read() {
+        let transaction = SpiTransaction {
+            mosi: vec![0x3b, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00],
+            miso: vec![0xff; 7],
+        };
+        assert_eq!(get_mailbox_access(&transaction, MAILBOX_ADDR, AddressMode::FourByte),
+            Some(MailboxAccess::DualOutputRead));
     }
 }
@@ -26,6 +26,7 @@ use spiutils::compat::firmware::BUILD_INFO_OFFSET;
 use spiutils::compat::firmware::BuildInfo;
 use spiutils::driver::firmware::SegmentInfo;
 use spiutils::driver::firmware::UNKNOWN_SEGMENT;
+use spiutils::protocol::firmware;
 use spiutils::protocol::wire::FromWire;
 
 #[derive(Copy, Clone, Debug)]
@@ -60,6 +61,11 @@ pub struct FirmwareController {
     write_segment: SegmentInfo,
     write_offset: usize,
     write_length: usize,
+
+    // The segment prepared for update and the offset up to which it has been
+    // written contiguously since.
+    update_segment: SegmentInfo,
+    update_offset: usize,
 }
 
 pub type FirmwareControllerResult<T> = Result<T, FirmwareControllerError>;
@@ -73,6 +79,8 @@ impl FirmwareController {
             write_segment: UNKNOWN_SEGMENT,
             write_offset: 0,
             write_length: 0,
+            update_segment: UNKNOWN_SEGMENT,
+            update_offset: 0,
         }
     }
 
@@ -159,6 +167,9 @@ impl FirmwareController {
     }
 
     pub fn erase_segment(&mut self, segment: SegmentInfo) -> FirmwareControllerResult<()> {
+        self.update_segment = UNKNOWN_SEGMENT;
+        self.update_offset = 0;
+
         self.erase_segment_start(segment)?;
         flash::get().wait_operation_done();
         self.check_operation_result()?;
@@ -166,19 +177,63 @@ impl FirmwareController {
             flash::get().wait_operation_done();
             self.check_operation_result()?;
         }
+
+        self.update_segment = segment;
         Ok(())
     }
 
-    pub fn write_and_verify_segment_chunk(&mut self, segment: SegmentInfo, offset: usize, data: &[u8]) -> FirmwareControllerResult<bool> {
+    fn write_and_verify_chunk(&mut self, segment: SegmentInfo, offset: usize, data: &[u8]) -> FirmwareControllerResult<bool> {
         self.write_segment_chunk(segment, offset, data)?;
         flash::get().wait_operation_done();
         self.check_operation_result()?;
         self.verify_segment_chunk()
     }
 
+    pub fn write_and_verify_segment_chunk(&mut self, segment: SegmentInfo, offset: usize, data: &[u8]) -> FirmwareControllerResult<bool> {
+        let result = self.write_and_verify_chunk(segment, offset, data);
+
+        if segment == self.update_segment {
+            match result {
+                Ok(true) => {
+                    if offset == self.update_offset {
+                        self.update_offset += data.len();
+                    }
+                }
+                _ => {
+                    // The data at offset can no longer be trusted, even if it
+                    // was verified before.
+                    if offset < self.update_offset {
+                        self.update_offset = offset;
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn read_segment_chunk(&self, segment: SegmentInfo, offset: usize, buf: &mut [u8]) -> FirmwareControllerResult<()> {
+        if flash::get().read(segment.address as usize + offset, buf, buf.len()).is_err() {
+            println!("flash read failed");
+            return Err(FirmwareControllerError::FlashReadError);
+        }
+
+        Ok(())
+    }
+
+    // Get the segment prepared for update and the offset up to which it has
+    // been written contiguously.
+    pub fn get_update_status(&self) -> (SegmentInfo, usize) {
+        (self.update_segment, self.update_offset)
+    }
+
     pub fn get_max_write_chunk_length(&self) -> usize {
         flash::MAX_BUFFER_LENGTH
     }
+
+    pub fn get_max_read_chunk_length(&self) -> usize {
+        firmware::MAX_READ_CHUNK_LEN
+    }
 }
 
 pub fn get_build_info(segment: SegmentInfo) -> TockResult<BuildInfo> {
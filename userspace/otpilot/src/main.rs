@@ -278,10 +278,10 @@ async fn main() -> TockResult<()> {
 
     println!("Starting {}", BANNER);
     println!("Reset source: {:?}", reset::get().get_reset_source()?);
-    println!("active RO: {:?}, {:?}", globalsec::get().get_active_ro(), firmware_controller::get_build_info(globalsec::get().get_active_ro())?);
-    println!("active RW: {:?}, {:?}", globalsec::get().get_active_rw(), firmware_controller::get_build_info(globalsec::get().get_active_rw())?);
-    println!("inactive RO: {:?}, {:?}", globalsec::get().get_inactive_ro(), firmware_controller::get_build_info(globalsec::get().get_inactive_ro())?);
-    println!("inactive RW: {:?}, {:?}", globalsec::get().get_inactive_rw(), firmware_controller::get_build_info(globalsec::get().get_inactive_rw())?);
+    println!("active RO: {}, {}", globalsec::get().get_active_ro(), firmware_controller::get_build_info(globalsec::get().get_active_ro())?);
+    println!("active RW: {}, {}", globalsec::get().get_active_rw(), firmware_controller::get_build_info(globalsec::get().get_active_rw())?);
+    println!("inactive RO: {}, {}", globalsec::get().get_inactive_ro(), firmware_controller::get_build_info(globalsec::get().get_inactive_ro())?);
+    println!("inactive RW: {}, {}", globalsec::get().get_inactive_rw(), firmware_controller::get_build_info(globalsec::get().get_inactive_rw())?);
     println!("DEV ID: 0x{:x}", fuse::get().get_dev_id()?);
     println!("clock_frequency: {}", alarm::get().get_clock_frequency());
 
@@ -63,7 +63,6 @@ pub enum SpiProcessorError {
     ToWire(ToWireError),
     Tock,
     Manticore(manticore_support::HandlerError),
-    UnsupportedFirmwareOperation(firmware::ContentType),
     UnsupportedOpCode(OpCode),
     InvalidAddress(Option<u32>),
     Format(core::fmt::Error),
@@ -209,6 +208,16 @@ impl<'a> SpiProcessor<'a> {
         self.send_firmware_response(response)
     }
 
+    fn process_firmware_active_segments(&mut self, mut data: &[u8]) -> SpiProcessorResult<()> {
+        let _ = firmware::ActiveSegmentsInfoRequest::from_wire(&mut data)?;
+
+        let response = firmware::ActiveSegmentsInfoResponse {
+            ro: globalsec::get().get_active_ro(),
+            rw: globalsec::get().get_active_rw(),
+        };
+        self.send_firmware_response(response)
+    }
+
     fn process_firmware_update_prepare(&mut self, mut data: &[u8]) -> SpiProcessorResult<()> {
         let req = firmware::UpdatePrepareRequest::from_wire(&mut data)?;
         let segment: SegmentInfo;
@@ -288,6 +297,61 @@ impl<'a> SpiProcessor<'a> {
         self.send_firmware_write_chunk_response(&req, result)
     }
 
+    fn send_firmware_read_chunk_response(&mut self, req: &firmware::ReadChunkRequest, result: firmware::ReadChunkResult, data: &[u8]) -> SpiProcessorResult<()> {
+        let response = firmware::ReadChunkResponse {
+            segment_and_location: req.segment_and_location,
+            offset: req.offset,
+            result: result,
+            data: data,
+        };
+        self.send_firmware_response(response)
+    }
+
+    fn process_firmware_read_chunk(&mut self, mut data: &[u8]) -> SpiProcessorResult<()> {
+        let req = firmware::ReadChunkRequest::from_wire(&mut data)?;
+        let segment: SegmentInfo;
+
+        if req.segment_and_location == globalsec::get().get_inactive_rw().identifier {
+            segment = globalsec::get().get_inactive_rw();
+        } else if req.segment_and_location == globalsec::get().get_inactive_ro().identifier {
+            segment = globalsec::get().get_inactive_ro();
+        } else {
+            return self.send_firmware_read_chunk_response(&req, firmware::ReadChunkResult::InvalidSegmentAndLocation, &[]);
+        }
+
+        if req.offset >= segment.size || req.offset % 4 != 0 {
+            return self.send_firmware_read_chunk_response(&req, firmware::ReadChunkResult::InvalidOffset, &[]);
+        }
+
+        let length = req.length as usize;
+        if length == 0 {
+            return self.send_firmware_read_chunk_response(&req, firmware::ReadChunkResult::InvalidLength, &[]);
+        }
+
+        if req.offset + req.length as u32 > segment.size || length > self.firmware.get_max_read_chunk_length() {
+            return self.send_firmware_read_chunk_response(&req, firmware::ReadChunkResult::DataTooLong, &[]);
+        }
+
+        let mut read_buf = [0u8; firmware::MAX_READ_CHUNK_LEN];
+        match self.firmware.read_segment_chunk(segment, req.offset as usize, &mut read_buf[..length]) {
+            Err(_why) => self.send_firmware_read_chunk_response(&req, firmware::ReadChunkResult::Error, &[]),
+            Ok(()) => self.send_firmware_read_chunk_response(&req, firmware::ReadChunkResult::Success, &read_buf[..length]),
+        }
+    }
+
+    fn process_firmware_update_status(&mut self, mut data: &[u8]) -> SpiProcessorResult<()> {
+        let _ = firmware::UpdateStatusRequest::from_wire(&mut data)?;
+
+        let (segment, offset) = self.firmware.get_update_status();
+        let next_offset: u32 = core::convert::TryFrom::<usize>::try_from(offset)
+            .map_err(|_| SpiProcessorError::FromWire(FromWireError::OutOfRange))?;
+        let response = firmware::UpdateStatusResponse {
+            segment_and_location: segment.identifier,
+            next_offset: next_offset,
+        };
+        self.send_firmware_response(response)
+    }
+
     fn send_firmware_reboot_response(&mut self, req: &firmware::RebootRequest, result: firmware::RebootResult) -> SpiProcessorResult<()> {
         let response = firmware::RebootResponse {
             time: req.time,
@@ -320,23 +384,42 @@ impl<'a> SpiProcessor<'a> {
     }
 
     fn process_firmware(&mut self, mut data: &[u8]) -> SpiProcessorResult<()> {
-        let header = firmware::Header::from_wire(&mut data)?;
+        let header = match firmware::Header::from_wire(&mut data) {
+            Ok(header) => header,
+            Err(FromWireError::OutOfRange) => {
+                // Let the host know that we don't support this request so that
+                // it can skip optional operations instead of timing out.
+                let error = error::ContentTypeNotSupported {};
+                return self.send_error(error);
+            },
+            Err(why) => return Err(why.into()),
+        };
 
         let result = match header.content {
             firmware::ContentType::InactiveSegmentsInfoRequest => {
                 self.process_firmware_inactive_segments(&mut data)
             },
+            firmware::ContentType::ActiveSegmentsInfoRequest => {
+                self.process_firmware_active_segments(&mut data)
+            },
             firmware::ContentType::UpdatePrepareRequest => {
                 self.process_firmware_update_prepare(&mut data)
             },
             firmware::ContentType::WriteChunkRequest => {
                 self.process_firmware_write_chunk(&mut data)
             },
+            firmware::ContentType::ReadChunkRequest => {
+                self.process_firmware_read_chunk(&mut data)
+            },
+            firmware::ContentType::UpdateStatusRequest => {
+                self.process_firmware_update_status(&mut data)
+            },
             firmware::ContentType::RebootRequest => {
                 self.process_firmware_reboot(&mut data)
             },
             _ => {
-                Err(SpiProcessorError::UnsupportedFirmwareOperation(header.content))
+                let error = error::ContentTypeNotSupported {};
+                self.send_error(error)
             }
         };
 